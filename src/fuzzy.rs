@@ -0,0 +1,101 @@
+//! Incremental subsequence fuzzy matching for the `/` filter.
+//!
+//! A name matches a query if the query's characters appear in order
+//! somewhere in the name (case-insensitive). Matches are scored so that
+//! consecutive runs and matches right after a word boundary (`/`, `_`,
+//! `-`, `.`, or a lower-to-upper camelCase transition) rank higher, similar
+//! to what fzf/skim do.
+
+/// Score `name` against `query`, or `None` if `query` isn't a subsequence
+/// of `name`. Higher scores are better matches. An empty query matches
+/// everything with a score of `0`.
+pub fn score(name: &str, query: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let name_chars: Vec<char> = name.chars().collect();
+    let query_chars: Vec<char> = query.to_lowercase().chars().collect();
+
+    let mut total = 0i64;
+    let mut qi = 0usize;
+    let mut prev_match: Option<usize> = None;
+
+    for (ni, &ch) in name_chars.iter().enumerate() {
+        if qi >= query_chars.len() {
+            break;
+        }
+        if ch.to_lowercase().next() != Some(query_chars[qi]) {
+            continue;
+        }
+
+        let mut char_score = 1i64;
+        if prev_match == Some(ni.wrapping_sub(1)) {
+            char_score += 5; // reward consecutive matched characters
+        }
+        if is_word_boundary(&name_chars, ni) {
+            char_score += 3; // reward matches right after a boundary
+        }
+
+        total += char_score;
+        prev_match = Some(ni);
+        qi += 1;
+    }
+
+    (qi == query_chars.len()).then_some(total)
+}
+
+/// True if `chars[idx]` starts a new "word": the very first character, or
+/// right after `/`, `_`, `-`, `.`, or a lowercase-to-uppercase transition.
+fn is_word_boundary(chars: &[char], idx: usize) -> bool {
+    if idx == 0 {
+        return true;
+    }
+    let prev = chars[idx - 1];
+    let cur = chars[idx];
+    matches!(prev, '/' | '_' | '-' | '.') || (prev.is_lowercase() && cur.is_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_query_matches_everything_with_zero_score() {
+        assert_eq!(score("anything.rs", ""), Some(0));
+    }
+
+    #[test]
+    fn non_subsequence_does_not_match() {
+        assert_eq!(score("main.rs", "xyz"), None);
+    }
+
+    #[test]
+    fn out_of_order_chars_do_not_match() {
+        assert_eq!(score("main.rs", "rm"), None);
+    }
+
+    #[test]
+    fn case_insensitive_subsequence_matches() {
+        assert!(score("Main.rs", "main").is_some());
+    }
+
+    #[test]
+    fn boundary_matches_outrank_mid_word_ones() {
+        // "mr" matches "main_render.rs" at two boundaries ("m" at the start,
+        // "r" after `_`), but in "mood_scanner.rs" only the "m" is at a
+        // boundary; its "r" is mid-word.
+        let boundary = score("main_render.rs", "mr").unwrap();
+        let scattered = score("mood_scanner.rs", "mr").unwrap();
+        assert!(boundary > scattered);
+    }
+
+    #[test]
+    fn word_boundary_is_true_at_start_and_after_separators() {
+        let chars: Vec<char> = "foo_Bar-baz.rs".chars().collect();
+        assert!(is_word_boundary(&chars, 0)); // start
+        assert!(is_word_boundary(&chars, 4)); // after '_', 'B'
+        assert!(is_word_boundary(&chars, 8)); // after '-'
+        assert!(!is_word_boundary(&chars, 1)); // mid-word
+    }
+}
@@ -1,21 +1,30 @@
 use std::{
+    collections::HashMap,
     fs,
     io,
     path::{Path, PathBuf},
-    time::SystemTime,
+    time::{Duration, Instant, SystemTime},
 };
 
+mod fuzzy;
+mod mount_list;
+mod preview;
+mod scan;
+mod tree;
+mod watch;
+
 use crossterm::{
     event::{self, Event, KeyCode, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use chrono::{DateTime, Local};
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
     Terminal,
 };
 
@@ -30,6 +39,63 @@ struct Entry {
     modified: Option<SystemTime>, // None if unknown/unreadable
 }
 
+/// How the left-hand panel lays out the current directory.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ViewMode {
+    /// Single-level listing of `cwd` (the original behavior).
+    Flat,
+    /// Indented, expandable tree rooted at `cwd`.
+    Tree,
+    /// Mounted filesystems overview, opened with `F`.
+    Mounts,
+}
+
+/// Listing sort key, cycled with `s`; direction flipped with `S`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SortMode {
+    Name,
+    Size,
+    Modified,
+}
+
+impl SortMode {
+    fn label(self) -> &'static str {
+        match self {
+            SortMode::Name => "name",
+            SortMode::Size => "size",
+            SortMode::Modified => "modified",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            SortMode::Name => SortMode::Size,
+            SortMode::Size => SortMode::Modified,
+            SortMode::Modified => SortMode::Name,
+        }
+    }
+}
+
+/// How key events are interpreted: a normal keymap, or capturing input for
+/// a confirmation/text-entry popup.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Normal,
+    Confirm,
+    Input,
+}
+
+/// A destructive action awaiting `y`/`n` confirmation.
+enum PendingAction {
+    Delete { path: PathBuf, name: String },
+}
+
+/// What the `Input` popup's text is for.
+enum InputPurpose {
+    Rename { path: PathBuf },
+    Mkdir,
+}
+
 /// The entire TUI application state.
 /// This is the core idea: state is pure data; UI renders it; input mutates it.
 struct App {
@@ -37,8 +103,62 @@ struct App {
     entries: Vec<Entry>,
     selected: usize,
     last_error: Option<String>, // surface errors in UI instead of crashing
+
+    /// Directory sizes computed by background scans, keyed by directory path.
+    /// Populated progressively as `active_scan` streams totals back.
+    scanned_size: HashMap<PathBuf, u64>,
+    /// The in-flight disk-usage scan, if any. `Esc` cancels it.
+    active_scan: Option<scan::ScanHandle>,
+
+    /// Flat listing vs. tree listing; toggled with `t`.
+    view_mode: ViewMode,
+    /// Tree roots for `cwd`, only populated/used in `ViewMode::Tree`.
+    tree_roots: Vec<tree::TreeNode>,
+    /// Cached flattening of `tree_roots`, recomputed whenever the tree changes.
+    tree_flat: Vec<tree::FlatRow>,
+
+    /// Whether `/` filter-entry mode is capturing keystrokes into `filter_query`.
+    filtering: bool,
+    /// The typed filter query, shown in the footer while `filtering`.
+    filter_query: String,
+    /// Indices into `entries` that survive the filter, in display order.
+    /// Equal to `0..entries.len()` when `filter_query` is empty.
+    filtered: Vec<usize>,
+
+    /// Loaded once; reused across every file previewed.
+    highlighter: preview::Highlighter,
+    /// Cached preview for the last-previewed file, keyed by path + mtime so
+    /// moving the cursor back and forth doesn't re-read and re-highlight.
+    preview_cache: Option<(PathBuf, Option<SystemTime>, preview::Preview)>,
+
+    /// Watches `cwd` for external changes; `None` if the watch failed.
+    watcher: Option<watch::Watcher>,
+    /// Set once an fs event arrives; cleared once the debounced refresh runs.
+    fs_event_pending: bool,
+    /// When the most recent (not-yet-acted-on) fs event arrived.
+    last_fs_event: Option<Instant>,
+
+    /// Mounted filesystems, populated when `ViewMode::Mounts` is entered.
+    mounts: Vec<mount_list::MountEntry>,
+
+    /// Active sort key for `entries`, cycled with `s`.
+    sort_mode: SortMode,
+    /// Sort direction; flipped with `S`.
+    sort_ascending: bool,
+
+    /// Normal keymap vs. confirm/input popup.
+    mode: Mode,
+    /// The action a `Confirm` popup is asking about.
+    confirm_action: Option<PendingAction>,
+    /// What an `Input` popup's text is for.
+    input_purpose: Option<InputPurpose>,
+    /// Text typed into the `Input` popup.
+    input_buffer: String,
 }
 
+/// Coalesce bursts of filesystem events before refreshing.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(100);
+
 impl App {
     /// Create a new app starting in the current working directory.
     fn new() -> io::Result<Self> {
@@ -48,8 +168,29 @@ impl App {
             entries: vec![],
             selected: 0,
             last_error: None,
+            scanned_size: HashMap::new(),
+            active_scan: None,
+            view_mode: ViewMode::Flat,
+            tree_roots: Vec::new(),
+            tree_flat: Vec::new(),
+            filtering: false,
+            filter_query: String::new(),
+            filtered: Vec::new(),
+            highlighter: preview::Highlighter::new(),
+            preview_cache: None,
+            watcher: None,
+            fs_event_pending: false,
+            last_fs_event: None,
+            mounts: Vec::new(),
+            sort_mode: SortMode::Name,
+            sort_ascending: true,
+            mode: Mode::Normal,
+            confirm_action: None,
+            input_purpose: None,
+            input_buffer: String::new(),
         };
         app.refresh(); // populate entries (errors go to last_error)
+        app.rewatch_cwd();
         Ok(app)
     }
 
@@ -103,56 +244,189 @@ impl App {
             });
         }
 
-        // Sort:
-        // 1) directories first
-        // 2) then by case-insensitive name
+        // Sort by the active `sort_mode`. Directories-first grouping only
+        // makes sense for name order; size/modified sort dirs and files
+        // together (using `scanned_size` for dirs that have been scanned).
         entries.sort_by(|a, b| {
-            match (a.is_dir, b.is_dir) {
-                (true, false) => std::cmp::Ordering::Less,
-                (false, true) => std::cmp::Ordering::Greater,
-                _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            let ord = match self.sort_mode {
+                SortMode::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                SortMode::Size => {
+                    let size_of = |e: &Entry| {
+                        e.size.or_else(|| self.scanned_size.get(&e.path).copied()).unwrap_or(0)
+                    };
+                    size_of(a).cmp(&size_of(b))
+                }
+                SortMode::Modified => a.modified.cmp(&b.modified),
+            };
+            let ord = if self.sort_ascending { ord } else { ord.reverse() };
+            if self.sort_mode == SortMode::Name {
+                // Directories stay first in either direction.
+                b.is_dir.cmp(&a.is_dir).then(ord)
+            } else {
+                ord
             }
         });
 
         self.entries = entries;
+        self.apply_filter();
+        self.clamp_selection();
+    }
+
+    /// Recompute `filtered` from `entries` and the current `filter_query`.
+    fn apply_filter(&mut self) {
+        if self.filter_query.is_empty() {
+            self.filtered = (0..self.entries.len()).collect();
+            return;
+        }
+
+        let mut scored: Vec<(usize, i64)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, e)| fuzzy::score(&e.name, &self.filter_query).map(|s| (i, s)))
+            .collect();
+
+        scored.sort_by(|&(ia, sa), &(ib, sb)| {
+            sb.cmp(&sa).then_with(|| {
+                let a = &self.entries[ia];
+                let b = &self.entries[ib];
+                match (a.is_dir, b.is_dir) {
+                    (true, false) => std::cmp::Ordering::Less,
+                    (false, true) => std::cmp::Ordering::Greater,
+                    _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                }
+            })
+        });
+
+        self.filtered = scored.into_iter().map(|(i, _)| i).collect();
+    }
+
+    /// The flat-mode entry at visible position `i`, going through `filtered`.
+    fn flat_entry(&self, i: usize) -> Option<&Entry> {
+        self.filtered.get(i).and_then(|&idx| self.entries.get(idx))
+    }
+
+    /// Enter filter-typing mode (`/`).
+    fn start_filter(&mut self) {
+        if self.view_mode != ViewMode::Flat {
+            return;
+        }
+        self.filtering = true;
+    }
+
+    /// Append a character to the filter query and re-narrow the listing.
+    fn filter_push(&mut self, c: char) {
+        self.filter_query.push(c);
+        self.apply_filter();
+        self.clamp_selection();
+    }
+
+    /// Remove the last character of the filter query and re-narrow.
+    fn filter_backspace(&mut self) {
+        self.filter_query.pop();
+        self.apply_filter();
+        self.clamp_selection();
+    }
+
+    /// Stop capturing keystrokes but keep the current filter applied.
+    fn filter_confirm(&mut self) {
+        self.filtering = false;
+    }
+
+    /// Clear the filter entirely and restore the full listing.
+    fn filter_clear(&mut self) {
+        self.filtering = false;
+        self.filter_query.clear();
+        self.apply_filter();
         self.clamp_selection();
     }
 
+    /// Number of rows visible in the current view mode.
+    fn visible_len(&self) -> usize {
+        match self.view_mode {
+            ViewMode::Flat => self.filtered.len(),
+            ViewMode::Tree => self.tree_flat.len(),
+            ViewMode::Mounts => self.mounts.len(),
+        }
+    }
+
     /// Make sure `selected` is always within bounds.
     fn clamp_selection(&mut self) {
-        if self.entries.is_empty() {
+        let len = self.visible_len();
+        if len == 0 {
             self.selected = 0;
             return;
         }
-        if self.selected >= self.entries.len() {
-            self.selected = self.entries.len() - 1;
+        if self.selected >= len {
+            self.selected = len - 1;
         }
     }
 
     /// Move selection by delta (+1 down, -1 up).
     fn move_selection(&mut self, delta: i32) {
-        if self.entries.is_empty() {
+        let len = self.visible_len();
+        if len == 0 {
             return;
         }
 
-        let len = self.entries.len() as i32;
+        let len = len as i32;
         let cur = self.selected as i32;
 
         let next = (cur + delta).clamp(0, len - 1);
         self.selected = next as usize;
     }
 
-    /// Enter the selected entry if it is a directory.
+    /// Enter the selected entry: in flat mode, descend into a directory; in
+    /// tree mode, toggle its expanded state instead.
     fn enter_selected_dir(&mut self) {
-        let Some(ent) = self.entries.get(self.selected).cloned() else {
+        match self.view_mode {
+            ViewMode::Flat => {
+                let Some(ent) = self.flat_entry(self.selected).cloned() else {
+                    return;
+                };
+                if ent.is_dir {
+                    self.cwd = ent.path;
+                    self.selected = 0;
+                    self.filter_clear();
+                    self.refresh();
+                    self.rebuild_tree();
+                    self.rewatch_cwd();
+                }
+            }
+            ViewMode::Tree => self.toggle_selected_expand(),
+            ViewMode::Mounts => self.enter_selected_mount(),
+        }
+    }
+
+    /// Toggle the expanded state of the selected tree row, lazily loading
+    /// its children on first expansion. No-op outside tree mode.
+    fn toggle_selected_expand(&mut self) {
+        if self.view_mode != ViewMode::Tree {
             return;
-        };
+        }
+        tree::toggle_visible(&mut self.tree_roots, self.selected);
+        self.tree_flat = tree::flatten(&self.tree_roots);
+        self.clamp_selection();
+    }
 
-        if ent.is_dir {
-            self.cwd = ent.path;
-            self.selected = 0;
-            self.refresh();
+    /// Switch between flat and tree listings, building the tree for `cwd`
+    /// the first time it's needed.
+    fn toggle_view_mode(&mut self) {
+        self.view_mode = match self.view_mode {
+            ViewMode::Flat => ViewMode::Tree,
+            ViewMode::Tree | ViewMode::Mounts => ViewMode::Flat,
+        };
+        if self.view_mode == ViewMode::Tree && self.tree_roots.is_empty() {
+            self.rebuild_tree();
         }
+        self.selected = 0;
+    }
+
+    /// Rebuild the (collapsed) tree for `cwd`. Called whenever `cwd` changes
+    /// so the tree is fresh the next time the user toggles into tree mode.
+    fn rebuild_tree(&mut self) {
+        self.tree_roots = tree::build_roots(&self.cwd);
+        self.tree_flat = tree::flatten(&self.tree_roots);
     }
 
     /// Move up to parent directory if possible.
@@ -160,16 +434,344 @@ impl App {
         if let Some(parent) = self.cwd.parent().map(Path::to_path_buf) {
             self.cwd = parent;
             self.selected = 0;
+            self.filter_clear();
             self.refresh();
+            self.rebuild_tree();
+            self.rewatch_cwd();
+        }
+    }
+
+    /// (Re)point the live filesystem watcher at `cwd`. Failures are
+    /// surfaced through `last_error` rather than crashing: the listing
+    /// still works, it just won't auto-refresh.
+    fn rewatch_cwd(&mut self) {
+        match watch::Watcher::new(&self.cwd) {
+            Ok(w) => self.watcher = Some(w),
+            Err(e) => {
+                self.watcher = None;
+                self.last_error = Some(format!("watch failed: {e}"));
+            }
         }
     }
 
-    /// Get selected entry (if any).
+    /// Cycle the sort key (name → size → modified → name) and re-sort.
+    fn cycle_sort_mode(&mut self) {
+        self.sort_mode = self.sort_mode.next();
+        self.refresh();
+    }
+
+    /// Flip sort direction and re-sort.
+    fn toggle_sort_direction(&mut self) {
+        self.sort_ascending = !self.sort_ascending;
+        self.refresh();
+    }
+
+    /// Ask for confirmation before trashing the selected entry (`d`). Goes
+    /// through `selected_entry()` rather than `flat_entry()` so this acts on
+    /// whatever row is actually highlighted in tree mode too, and is a no-op
+    /// in mounts mode (where there's no file row to act on).
+    fn start_delete_selected(&mut self) {
+        let Some(e) = self.selected_entry() else {
+            return;
+        };
+        self.confirm_action = Some(PendingAction::Delete {
+            path: e.path.clone(),
+            name: e.name.clone(),
+        });
+        self.mode = Mode::Confirm;
+    }
+
+    /// User answered `y` to the confirm popup: perform the pending action.
+    fn confirm_yes(&mut self) {
+        let mut error = None;
+        if let Some(PendingAction::Delete { path, .. }) = self.confirm_action.take() {
+            if let Err(e) = trash::delete(&path) {
+                error = Some(format!("delete to trash failed: {e}"));
+            }
+        }
+        self.mode = Mode::Normal;
+        self.refresh_preserving_selection();
+        // Set after the refresh, which clears `last_error`.
+        if error.is_some() {
+            self.last_error = error;
+        }
+    }
+
+    /// User answered `n` (or cancelled) the confirm popup.
+    fn confirm_no(&mut self) {
+        self.confirm_action = None;
+        self.mode = Mode::Normal;
+    }
+
+    /// Open the rename popup for the selected entry, pre-filled with its
+    /// current name (`R`). Goes through `selected_entry()` rather than
+    /// `flat_entry()` so this acts on whatever row is actually highlighted
+    /// in tree mode too, and is a no-op in mounts mode.
+    fn start_rename_selected(&mut self) {
+        let Some((path, name)) = self.selected_entry().map(|e| (e.path.clone(), e.name.clone()))
+        else {
+            return;
+        };
+        self.input_purpose = Some(InputPurpose::Rename { path });
+        self.input_buffer = name;
+        self.mode = Mode::Input;
+    }
+
+    /// Open the new-directory popup (`m`).
+    fn start_mkdir(&mut self) {
+        self.input_purpose = Some(InputPurpose::Mkdir);
+        self.input_buffer.clear();
+        self.mode = Mode::Input;
+    }
+
+    fn input_push(&mut self, c: char) {
+        self.input_buffer.push(c);
+    }
+
+    fn input_backspace(&mut self) {
+        self.input_buffer.pop();
+    }
+
+    fn input_cancel(&mut self) {
+        self.input_purpose = None;
+        self.input_buffer.clear();
+        self.mode = Mode::Normal;
+    }
+
+    /// Perform the pending rename/mkdir with the typed name, then refresh
+    /// and re-select the entry by its new name.
+    fn input_confirm(&mut self) {
+        let purpose = self.input_purpose.take();
+        let name = std::mem::take(&mut self.input_buffer).trim().to_string();
+        self.mode = Mode::Normal;
+
+        let (Some(purpose), false) = (purpose, name.is_empty()) else {
+            return;
+        };
+
+        let mut error = None;
+        match purpose {
+            InputPurpose::Rename { path } => {
+                let target = path.parent().map_or_else(|| PathBuf::from(&name), |p| p.join(&name));
+                if let Err(e) = fs::rename(&path, &target) {
+                    error = Some(format!("rename failed: {e}"));
+                }
+            }
+            InputPurpose::Mkdir => {
+                let target = self.cwd.join(&name);
+                if let Err(e) = fs::create_dir(&target) {
+                    error = Some(format!("mkdir failed: {e}"));
+                }
+            }
+        }
+
+        self.refresh();
+        if self.view_mode == ViewMode::Tree {
+            self.rebuild_tree();
+        }
+        // Set after the refresh, which clears `last_error`.
+        if error.is_some() {
+            self.last_error = error;
+        }
+        self.select_by_name(&name);
+        self.clamp_selection();
+    }
+
+    /// Open the mounted-filesystems overview (`F`). Parse/stat failures are
+    /// surfaced through `last_error` rather than crashing.
+    fn open_mounts(&mut self) {
+        match mount_list::list_mounts() {
+            Ok(mounts) => {
+                self.mounts = mounts;
+                self.last_error = None;
+            }
+            Err(e) => {
+                self.mounts.clear();
+                self.last_error = Some(e);
+            }
+        }
+        self.view_mode = ViewMode::Mounts;
+        self.selected = 0;
+    }
+
+    /// Leave the mounted-filesystems overview without changing `cwd`.
+    fn close_mounts(&mut self) {
+        if self.view_mode == ViewMode::Mounts {
+            self.view_mode = ViewMode::Flat;
+            self.selected = 0;
+        }
+    }
+
+    /// `cd` into the selected mount point and return to the normal listing.
+    fn enter_selected_mount(&mut self) {
+        let Some(mount_point) = self.mounts.get(self.selected).map(|m| m.mount_point.clone())
+        else {
+            return;
+        };
+
+        self.cwd = mount_point;
+        self.view_mode = ViewMode::Flat;
+        self.selected = 0;
+        self.filter_clear();
+        self.refresh();
+        self.rebuild_tree();
+        self.rewatch_cwd();
+    }
+
+    /// Poll the watcher and, once a burst of events has gone quiet for
+    /// `WATCH_DEBOUNCE`, refresh the listing while preserving the selected
+    /// entry by name.
+    fn poll_watcher(&mut self) {
+        if let Some(w) = &self.watcher {
+            if w.poll() {
+                self.fs_event_pending = true;
+                self.last_fs_event = Some(Instant::now());
+            }
+        }
+
+        let debounced = self
+            .last_fs_event
+            .is_some_and(|t| t.elapsed() >= WATCH_DEBOUNCE);
+
+        if self.fs_event_pending && debounced {
+            self.fs_event_pending = false;
+            self.refresh_preserving_selection();
+        }
+    }
+
+    /// Refresh the listing (and, in tree mode, the tree too), then re-locate
+    /// the previously selected entry by name, falling back to a clamped
+    /// index if it disappeared.
+    fn refresh_preserving_selection(&mut self) {
+        let prev_name = self.selected_entry().map(|e| e.name.clone());
+
+        self.refresh();
+        if self.view_mode == ViewMode::Tree {
+            self.rebuild_tree();
+        }
+
+        if let Some(name) = prev_name {
+            self.select_by_name(&name);
+        }
+        self.clamp_selection();
+    }
+
+    /// Move `selected` onto the entry named `name` in whichever view mode is
+    /// active, if present. No-op (selection stays wherever it was) if `name`
+    /// isn't found, or in mounts mode where there's nothing to match.
+    fn select_by_name(&mut self, name: &str) {
+        let pos = match self.view_mode {
+            ViewMode::Flat => self
+                .filtered
+                .iter()
+                .position(|&idx| self.entries.get(idx).is_some_and(|e| e.name == name)),
+            ViewMode::Tree => self.tree_flat.iter().position(|row| row.entry.name == name),
+            ViewMode::Mounts => None,
+        };
+        if let Some(pos) = pos {
+            self.selected = pos;
+        }
+    }
+
+    /// Get selected entry (if any), in whichever view mode is active.
     fn selected_entry(&self) -> Option<&Entry> {
-        self.entries.get(self.selected)
+        match self.view_mode {
+            ViewMode::Flat => self.flat_entry(self.selected),
+            ViewMode::Tree => self.tree_flat.get(self.selected).map(|row| &row.entry),
+            ViewMode::Mounts => None,
+        }
+    }
+
+    /// Kick off a background disk-usage scan of the selected directory.
+    /// Replaces any scan already running.
+    fn start_scan_selected(&mut self) {
+        let Some(target) = self.selected_entry().filter(|e| e.is_dir).map(|e| e.path.clone())
+        else {
+            return;
+        };
+
+        if let Some(prev) = self.active_scan.take() {
+            prev.cancel();
+        }
+        self.active_scan = Some(scan::ScanHandle::spawn(target));
+    }
+
+    /// Abort the in-flight scan, if any.
+    fn cancel_scan(&mut self) {
+        if let Some(scan) = self.active_scan.take() {
+            scan.cancel();
+        }
+    }
+
+    /// Drain any pending scan progress messages into `scanned_size`.
+    /// Called once per poll tick so the UI updates progressively.
+    fn poll_scan(&mut self) {
+        let Some(scan) = &self.active_scan else {
+            return;
+        };
+
+        let mut finished = false;
+        for (root, bytes, done) in scan.drain() {
+            self.scanned_size.insert(root, bytes);
+            if done {
+                finished = true;
+            }
+        }
+
+        if finished {
+            self.active_scan = None;
+        }
+    }
+
+    /// Recompute the cached preview if the selection moved to a different
+    /// file, or that file's mtime changed since it was last previewed.
+    fn refresh_preview(&mut self) {
+        let Some(entry) = self.selected_entry().cloned() else {
+            self.preview_cache = None;
+            return;
+        };
+
+        if entry.is_dir {
+            self.preview_cache = None;
+            return;
+        }
+
+        let metadata = fs::metadata(&entry.path);
+        let mtime = metadata.as_ref().ok().and_then(|m| m.modified().ok());
+        let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
+
+        if let Some((cached_path, cached_mtime, _)) = &self.preview_cache {
+            if *cached_path == entry.path && *cached_mtime == mtime {
+                return; // still fresh
+            }
+        }
+
+        let preview = self.highlighter.load(&entry.path, size);
+        self.preview_cache = Some((entry.path, mtime, preview));
     }
 }
 
+/// A `percent_x` × `percent_y` rect centered within `area`, for popups.
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
+
 /// Convert bytes to a rough human readable size.
 /// (Deliberately minimal; you can refine formatting later.)
 fn human_size(bytes: u64) -> String {
@@ -189,6 +791,42 @@ fn human_size(bytes: u64) -> String {
     }
 }
 
+/// Format a modified time as an absolute local timestamp plus a relative
+/// age, e.g. `2026-07-23 09:14:02 (3 days ago)`.
+fn format_modified(modified: Option<SystemTime>) -> String {
+    let Some(t) = modified else {
+        return "-".to_string();
+    };
+
+    let local: DateTime<Local> = t.into();
+    format!("{} ({})", local.format("%Y-%m-%d %H:%M:%S"), relative_age(t))
+}
+
+/// Coarse "N units ago" rendering of `t` relative to now.
+fn relative_age(t: SystemTime) -> String {
+    let secs = SystemTime::now().duration_since(t).unwrap_or_default().as_secs();
+
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+    const MONTH: u64 = 30 * DAY;
+    const YEAR: u64 = 365 * DAY;
+
+    if secs < MINUTE {
+        "just now".to_string()
+    } else if secs < HOUR {
+        format!("{} minutes ago", secs / MINUTE)
+    } else if secs < DAY {
+        format!("{} hours ago", secs / HOUR)
+    } else if secs < MONTH {
+        format!("{} days ago", secs / DAY)
+    } else if secs < YEAR {
+        format!("{} months ago", secs / MONTH)
+    } else {
+        format!("{} years ago", secs / YEAR)
+    }
+}
+
 /// Main entry point.
 /// Responsibilities:
 /// - setup terminal (raw mode + alternate screen)
@@ -223,8 +861,17 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> io::Result<
     let mut list_state = ListState::default();
 
     loop {
-        // Keep the UI selection state in sync with app.selected
-        if app.entries.is_empty() {
+        // Drain any disk-usage scan progress so the UI updates even if no
+        // key was pressed this tick.
+        app.poll_scan();
+        app.poll_watcher();
+        app.refresh_preview();
+
+        // Keep the UI selection state in sync with app.selected. Gate on the
+        // active view's row count, not `entries.len()` — Tree and Mounts
+        // views have their own row counts that can be non-empty even when
+        // `entries` (the flat listing) is.
+        if app.visible_len() == 0 {
             list_state.select(None);
         } else {
             list_state.select(Some(app.selected));
@@ -251,33 +898,97 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> io::Result<
             // --- Header ---
             let header = Paragraph::new(Line::from(vec![
                 Span::raw("Rota File Manager  "),
-                Span::styled("Phase 0 (read-only)", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled("Phase 1 (file ops)", Style::default().add_modifier(Modifier::BOLD)),
             ]))
             .block(Block::default().borders(Borders::ALL).title(app.cwd.display().to_string()));
             f.render_widget(header, left[0]);
 
             // --- List items ---
-            let items: Vec<ListItem> = app
-                .entries
-                .iter()
-                .map(|e| {
-                    // Keep it ASCII-clean for now.
-                    let prefix = if e.is_dir { "[DIR] " } else { "      " };
-                    ListItem::new(Line::from(format!("{prefix}{}", e.name)))
-                })
-                .collect();
+            let (items, list_title): (Vec<ListItem>, String) = match app.view_mode {
+                ViewMode::Flat => {
+                    let items = app
+                        .filtered
+                        .iter()
+                        .filter_map(|&idx| app.entries.get(idx))
+                        .map(|e| {
+                            // Keep it ASCII-clean for now.
+                            let kind_prefix = if e.is_dir { "[DIR] " } else { "      " };
+                            ListItem::new(Line::from(format!("{kind_prefix}{}", e.name)))
+                        })
+                        .collect();
+                    let title = if app.filter_query.is_empty() {
+                        "Entries".to_string()
+                    } else {
+                        format!("Entries (filter: {})", app.filter_query)
+                    };
+                    (items, title)
+                }
+                ViewMode::Tree => {
+                    let items = app
+                        .tree_flat
+                        .iter()
+                        .map(|row| {
+                            let marker = if row.entry.is_dir {
+                                if row.expanded { "v " } else { "> " }
+                            } else {
+                                "  "
+                            };
+                            ListItem::new(Line::from(format!(
+                                "{}{}{}",
+                                row.prefix, marker, row.entry.name
+                            )))
+                        })
+                        .collect();
+                    (items, "Entries (tree)".to_string())
+                }
+                ViewMode::Mounts => {
+                    let items = app
+                        .mounts
+                        .iter()
+                        .map(|m| {
+                            let pct = if m.total > 0 {
+                                (m.used as f64 / m.total as f64 * 100.0).round() as u32
+                            } else {
+                                0
+                            };
+                            let bar_width = 10usize;
+                            let filled = ((pct as usize) * bar_width / 100).min(bar_width);
+                            let bar =
+                                format!("[{}{}]", "#".repeat(filled), "-".repeat(bar_width - filled));
+
+                            ListItem::new(Line::from(format!(
+                                "{:<24} {:<8} {bar} {pct:>3}%  {:>9} / {:<9}  {}",
+                                m.mount_point.display().to_string(),
+                                m.fstype,
+                                human_size(m.used),
+                                human_size(m.total),
+                                m.device,
+                            )))
+                        })
+                        .collect();
+                    (items, "Mounted Filesystems".to_string())
+                }
+            };
 
             let list = List::new(items)
-                .block(Block::default().borders(Borders::ALL).title("Entries"))
+                .block(Block::default().borders(Borders::ALL).title(list_title))
                 .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
 
             f.render_stateful_widget(list, left[1], &mut list_state);
 
             // --- Footer / status ---
-            let help = if let Some(err) = &app.last_error {
+            let help = if app.filtering || !app.filter_query.is_empty() {
+                format!("Filter: {}_  (Enter keep | Esc clear)", app.filter_query)
+            } else if let Some(err) = &app.last_error {
                 format!("ERROR: {err}")
+            } else if app.view_mode == ViewMode::Mounts {
+                "Keys: j/k move | Enter cd here | F/Esc back | q quit".to_string()
             } else {
-                "Keys: j/k or ↑/↓ move | Enter open dir | Backspace up | r refresh | q quit".to_string()
+                let dir_arrow = if app.sort_ascending { "▲" } else { "▼" };
+                format!(
+                    "Keys: j/k or ↑/↓ move | Enter open/toggle | Backspace up | t tree | / filter | F filesystems | d delete | R rename | m mkdir | r refresh | u scan size | Esc cancel scan | q quit  ||  Sort: {} {dir_arrow} (s cycle, S flip)",
+                    app.sort_mode.label()
+                )
             };
 
             let footer = Paragraph::new(help)
@@ -286,32 +997,120 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> io::Result<
             f.render_widget(footer, left[2]);
 
             // --- Right side: details panel ---
-            let detail_text = match app.selected_entry() {
-                None => "No entries".to_string(),
+            let mut detail_lines: Vec<Line> = Vec::new();
+            if app.view_mode == ViewMode::Mounts {
+                match app.mounts.get(app.selected) {
+                    None => detail_lines.push(Line::from("No mounts")),
+                    Some(m) => {
+                        detail_lines.push(Line::from(format!("Mount point: {}", m.mount_point.display())));
+                        detail_lines.push(Line::from(format!("Device: {}", m.device)));
+                        detail_lines.push(Line::from(format!("Type: {}", m.fstype)));
+                        detail_lines.push(Line::from(format!("Total: {}", human_size(m.total))));
+                        detail_lines.push(Line::from(format!("Used: {}", human_size(m.used))));
+                        detail_lines.push(Line::from(format!("Available: {}", human_size(m.available))));
+                    }
+                }
+            } else {
+            match app.selected_entry() {
+                None => detail_lines.push(Line::from("No entries")),
                 Some(e) => {
                     let kind = if e.is_dir { "Directory" } else { "File" };
-                    let size = e.size.map(human_size).unwrap_or_else(|| "-".to_string());
-                    let modified = match e.modified {
-                        None => "-".to_string(),
-                        Some(_t) => "known (format later)".to_string(), // keep minimal now
+                    let size = if e.is_dir {
+                        match (app.scanned_size.get(&e.path), &app.active_scan) {
+                            (Some(bytes), Some(scan)) if scan.root() == e.path => {
+                                format!("{} (scanning…)", human_size(*bytes))
+                            }
+                            (Some(bytes), _) => human_size(*bytes),
+                            (None, Some(scan)) if scan.root() == e.path => {
+                                "scanning…".to_string()
+                            }
+                            (None, _) => "-".to_string(),
+                        }
+                    } else {
+                        e.size.map(human_size).unwrap_or_else(|| "-".to_string())
                     };
+                    let modified = format_modified(e.modified);
+
+                    detail_lines.push(Line::from(format!("Name: {}", e.name)));
+                    detail_lines.push(Line::from(format!("Type: {kind}")));
+                    detail_lines.push(Line::from(format!("Size: {size}")));
+                    detail_lines.push(Line::from(format!("Modified: {modified}")));
+                    detail_lines.push(Line::from(""));
+                    detail_lines.push(Line::from("Path:"));
+                    detail_lines.push(Line::from(e.path.display().to_string()));
 
-                    format!(
-                        "Name: {}\nType: {}\nSize: {}\nModified: {}\n\nPath:\n{}",
-                        e.name,
-                        kind,
-                        size,
-                        modified,
-                        e.path.display()
-                    )
+                    if !e.is_dir {
+                        detail_lines.push(Line::from(""));
+                        detail_lines.push(Line::from(Span::styled(
+                            "Preview:",
+                            Style::default().add_modifier(Modifier::BOLD),
+                        )));
+
+                        match app.preview_cache.as_ref().filter(|(p, ..)| *p == e.path) {
+                            Some((_, _, preview::Preview::Text(lines))) => {
+                                detail_lines.extend(lines.iter().cloned())
+                            }
+                            Some((_, _, preview::Preview::Binary(lines))) => {
+                                detail_lines.extend(lines.iter().cloned())
+                            }
+                            Some((_, _, preview::Preview::TooLarge { size, cap })) => {
+                                detail_lines.push(Line::from(format!(
+                                    "(preview skipped: {} exceeds {} limit)",
+                                    human_size(*size),
+                                    human_size(*cap)
+                                )));
+                            }
+                            Some((_, _, preview::Preview::Error(msg))) => {
+                                detail_lines.push(Line::from(format!("(preview error: {msg})")));
+                            }
+                            None => detail_lines.push(Line::from("(loading…)")),
+                        }
+                    }
                 }
             };
+            }
 
-            let details = Paragraph::new(detail_text)
+            let details = Paragraph::new(detail_lines)
                 .block(Block::default().borders(Borders::ALL).title("Details"))
                 .wrap(Wrap { trim: false });
 
             f.render_widget(details, chunks[1]);
+
+            // --- Confirm/Input popups (drawn last so they sit on top) ---
+            match app.mode {
+                Mode::Normal => {}
+                Mode::Confirm => {
+                    let text = match &app.confirm_action {
+                        Some(PendingAction::Delete { name, .. }) => {
+                            format!("Delete \"{name}\" to trash?\n\n(y)es / (n)o")
+                        }
+                        None => String::new(),
+                    };
+                    let area = centered_rect(50, 20, f.area());
+                    f.render_widget(Clear, area);
+                    f.render_widget(
+                        Paragraph::new(text)
+                            .block(Block::default().borders(Borders::ALL).title("Confirm"))
+                            .wrap(Wrap { trim: false }),
+                        area,
+                    );
+                }
+                Mode::Input => {
+                    let title = match &app.input_purpose {
+                        Some(InputPurpose::Rename { .. }) => "Rename to",
+                        Some(InputPurpose::Mkdir) => "New directory name",
+                        None => "Input",
+                    };
+                    let area = centered_rect(50, 20, f.area());
+                    f.render_widget(Clear, area);
+                    f.render_widget(
+                        Paragraph::new(format!("{}_", app.input_buffer))
+                            .block(Block::default().borders(Borders::ALL).title(title))
+                            .wrap(Wrap { trim: false }),
+                        area,
+                    );
+                }
+            }
         })?;
 
         // --- Input ---
@@ -323,18 +1122,79 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> io::Result<
                     continue;
                 }
 
-                match key.code {
-                    KeyCode::Char('q') => break,
+                match app.mode {
+                    Mode::Confirm => match key.code {
+                        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => app.confirm_yes(),
+                        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.confirm_no(),
+                        _ => {}
+                    },
+
+                    Mode::Input => match key.code {
+                        KeyCode::Esc => app.input_cancel(),
+                        KeyCode::Enter => app.input_confirm(),
+                        KeyCode::Backspace => app.input_backspace(),
+                        KeyCode::Char(c) => app.input_push(c),
+                        _ => {}
+                    },
+
+                    Mode::Normal if app.filtering => {
+                        // While capturing a filter query, typed characters narrow
+                        // the listing instead of being interpreted as commands.
+                        match key.code {
+                            KeyCode::Esc => app.filter_clear(),
+                            KeyCode::Enter => app.filter_confirm(),
+                            KeyCode::Backspace => app.filter_backspace(),
+                            KeyCode::Down => app.move_selection(1),
+                            KeyCode::Up => app.move_selection(-1),
+                            KeyCode::Char(c) => app.filter_push(c),
+                            _ => {}
+                        }
+                    }
+
+                    Mode::Normal => match key.code {
+                        KeyCode::Char('q') => break,
+
+                        KeyCode::Down | KeyCode::Char('j') => app.move_selection(1),
+                        KeyCode::Up | KeyCode::Char('k') => app.move_selection(-1),
+
+                        KeyCode::Enter => app.enter_selected_dir(),
+                        KeyCode::Backspace => app.go_parent(),
+
+                        KeyCode::Char('r') => app.refresh(),
+
+                        KeyCode::Char('u') => app.start_scan_selected(),
+                        KeyCode::Esc => {
+                            if app.view_mode == ViewMode::Mounts {
+                                app.close_mounts();
+                            } else if app.filter_query.is_empty() {
+                                app.cancel_scan();
+                            } else {
+                                app.filter_clear();
+                            }
+                        }
+
+                        KeyCode::Char('t') => app.toggle_view_mode(),
+                        KeyCode::Char('z') => app.toggle_selected_expand(),
+
+                        KeyCode::Char('/') => app.start_filter(),
 
-                    KeyCode::Down | KeyCode::Char('j') => app.move_selection(1),
-                    KeyCode::Up | KeyCode::Char('k') => app.move_selection(-1),
+                        KeyCode::Char('F') => {
+                            if app.view_mode == ViewMode::Mounts {
+                                app.close_mounts();
+                            } else {
+                                app.open_mounts();
+                            }
+                        }
 
-                    KeyCode::Enter => app.enter_selected_dir(),
-                    KeyCode::Backspace => app.go_parent(),
+                        KeyCode::Char('s') => app.cycle_sort_mode(),
+                        KeyCode::Char('S') => app.toggle_sort_direction(),
 
-                    KeyCode::Char('r') => app.refresh(),
+                        KeyCode::Char('d') => app.start_delete_selected(),
+                        KeyCode::Char('R') => app.start_rename_selected(),
+                        KeyCode::Char('m') => app.start_mkdir(),
 
-                    _ => {}
+                        _ => {}
+                    },
                 }
             }
         }
@@ -0,0 +1,122 @@
+//! Mounted filesystems overview: parses `/proc/mounts` for the list of
+//! mount points and uses `statvfs` (via `libc`) for capacity/free figures,
+//! analogous to broot's `:filesystems`.
+
+use std::{fs, path::PathBuf};
+
+pub struct MountEntry {
+    pub mount_point: PathBuf,
+    pub device: String,
+    pub fstype: String,
+    pub total: u64,
+    pub used: u64,
+    pub available: u64,
+}
+
+/// Read and stat every mount in `/proc/mounts`. A failure to read the file
+/// itself is fatal (returned as `Err`); a failure to `statvfs` one mount
+/// just leaves that entry's sizes at zero rather than dropping the row.
+pub fn list_mounts() -> Result<Vec<MountEntry>, String> {
+    let content =
+        fs::read_to_string("/proc/mounts").map_err(|e| format!("reading /proc/mounts: {e}"))?;
+
+    let mut mounts = Vec::new();
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        let (Some(device), Some(mount_point_raw), Some(fstype)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+
+        let mount_point = PathBuf::from(unescape(mount_point_raw));
+        let (total, used, available) = statvfs_usage(&mount_point).unwrap_or((0, 0, 0));
+
+        mounts.push(MountEntry {
+            mount_point,
+            device: unescape(device),
+            fstype: fstype.to_string(),
+            total,
+            used,
+            available,
+        });
+    }
+
+    Ok(mounts)
+}
+
+/// `/proc/mounts` escapes space, tab, newline and backslash as `\NNN` octal.
+fn unescape(field: &str) -> String {
+    let chars: Vec<char> = field.chars().collect();
+    let mut out = String::with_capacity(chars.len());
+
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '\\' && i + 3 < chars.len() {
+            let code: String = chars[i + 1..i + 4].iter().collect();
+            if let Ok(byte) = u8::from_str_radix(&code, 8) {
+                out.push(byte as char);
+                i += 4;
+                continue;
+            }
+        }
+        out.push(chars[i]);
+        i += 1;
+    }
+
+    out
+}
+
+/// `(total_bytes, used_bytes, available_to_user_bytes)` for the filesystem
+/// mounted at `mount_point`, or `None` if `statvfs` fails (e.g. the mount
+/// point is a stale bind mount).
+fn statvfs_usage(mount_point: &std::path::Path) -> Option<(u64, u64, u64)> {
+    use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+    let c_path = CString::new(mount_point.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    let rc = unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) };
+    if rc != 0 {
+        return None;
+    }
+
+    let block_size = stat.f_frsize as u64;
+    let total = stat.f_blocks as u64 * block_size;
+    let free = stat.f_bfree as u64 * block_size;
+    let available = stat.f_bavail as u64 * block_size;
+
+    Some((total, total.saturating_sub(free), available))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unescapes_octal_space() {
+        assert_eq!(unescape(r"\040"), " ");
+        assert_eq!(unescape(r"mnt\040point"), "mnt point");
+    }
+
+    #[test]
+    fn unescapes_tab_and_newline_and_backslash() {
+        assert_eq!(unescape(r"\011"), "\t");
+        assert_eq!(unescape(r"\012"), "\n");
+        assert_eq!(unescape(r"\134"), "\\");
+    }
+
+    #[test]
+    fn leaves_plain_text_untouched() {
+        assert_eq!(unescape("/mnt/data"), "/mnt/data");
+    }
+
+    #[test]
+    fn trailing_backslash_is_kept_literally() {
+        assert_eq!(unescape(r"mnt\"), "mnt\\");
+    }
+
+    #[test]
+    fn non_octal_escape_is_kept_literally() {
+        assert_eq!(unescape(r"\abc"), "\\abc");
+    }
+}
@@ -0,0 +1,139 @@
+//! File preview for the Details panel: syntax-highlighted text for source
+//! files, a hex-dump fallback for binaries, detected by sniffing for NUL
+//! bytes in the sampled prefix.
+//!
+//! Only the first [`PREVIEW_PREFIX_BYTES`] of a file are ever read, and
+//! files above [`MAX_PREVIEWABLE_SIZE`] are skipped entirely, so moving the
+//! cursor over a huge file never stalls the UI. Callers are expected to
+//! cache the result keyed by path + mtime (see `App::preview_cache`) so
+//! moving the cursor back and forth doesn't re-read and re-highlight.
+
+use std::{fs::File, io::Read, path::Path};
+
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{Theme, ThemeSet},
+    parsing::SyntaxSet,
+    util::LinesWithEndings,
+};
+
+/// Skip previewing files larger than this entirely.
+pub const MAX_PREVIEWABLE_SIZE: u64 = 10 * 1024 * 1024;
+/// Only read/highlight this many bytes from the start of a file.
+const PREVIEW_PREFIX_BYTES: usize = 64 * 1024;
+/// How many bytes of the prefix to sniff for NUL bytes (binary detection).
+const SNIFF_BYTES: usize = 8 * 1024;
+
+#[derive(Clone)]
+pub enum Preview {
+    Text(Vec<Line<'static>>),
+    Binary(Vec<Line<'static>>),
+    TooLarge { size: u64, cap: u64 },
+    Error(String),
+}
+
+/// Owns the syntect syntax/theme sets so they're loaded once, not per file.
+pub struct Highlighter {
+    syntax_set: SyntaxSet,
+    theme: Theme,
+}
+
+impl Highlighter {
+    pub fn new() -> Self {
+        let syntax_set = SyntaxSet::load_defaults_newlines();
+        let theme = ThemeSet::load_defaults().themes["base16-ocean.dark"].clone();
+        Self { syntax_set, theme }
+    }
+
+    /// Load and (if applicable) highlight a preview of `path`, whose total
+    /// size is `size`.
+    pub fn load(&self, path: &Path, size: u64) -> Preview {
+        if size > MAX_PREVIEWABLE_SIZE {
+            return Preview::TooLarge {
+                size,
+                cap: MAX_PREVIEWABLE_SIZE,
+            };
+        }
+
+        let file = match File::open(path) {
+            Ok(f) => f,
+            Err(e) => return Preview::Error(e.to_string()),
+        };
+
+        let mut buf = Vec::with_capacity(PREVIEW_PREFIX_BYTES.min(size as usize + 1));
+        if let Err(e) = file.take(PREVIEW_PREFIX_BYTES as u64).read_to_end(&mut buf) {
+            return Preview::Error(e.to_string());
+        }
+
+        let sniff_len = buf.len().min(SNIFF_BYTES);
+        if buf[..sniff_len].contains(&0) {
+            return Preview::Binary(hex_dump(&buf));
+        }
+
+        Preview::Text(self.highlight(path, &buf))
+    }
+
+    fn highlight(&self, path: &Path, buf: &[u8]) -> Vec<Line<'static>> {
+        let text = String::from_utf8_lossy(buf);
+
+        let syntax = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| self.syntax_set.find_syntax_by_extension(ext))
+            .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text());
+
+        let mut highlighter = HighlightLines::new(syntax, &self.theme);
+        let mut lines = Vec::new();
+
+        for line in LinesWithEndings::from(&text) {
+            let ranges = highlighter
+                .highlight_line(line, &self.syntax_set)
+                .unwrap_or_default();
+
+            let spans = ranges
+                .into_iter()
+                .map(|(style, piece)| {
+                    let fg = style.foreground;
+                    Span::styled(
+                        piece.trim_end_matches(['\n', '\r']).to_string(),
+                        Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b)),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            lines.push(Line::from(spans));
+        }
+
+        lines
+    }
+}
+
+/// Render `bytes` as classic `offset  hex...  ascii` rows.
+fn hex_dump(bytes: &[u8]) -> Vec<Line<'static>> {
+    bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let offset = i * 16;
+
+            let mut hex = String::with_capacity(48);
+            for b in chunk {
+                hex.push_str(&format!("{b:02x} "));
+            }
+            for _ in chunk.len()..16 {
+                hex.push_str("   ");
+            }
+
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| if (0x20..0x7f).contains(&b) { b as char } else { '.' })
+                .collect();
+
+            Line::from(format!("{offset:08x}  {hex} {ascii}"))
+        })
+        .collect()
+}
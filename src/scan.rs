@@ -0,0 +1,111 @@
+//! Background recursive disk-usage scanning.
+//!
+//! A scan walks a directory tree on its own thread and streams running byte
+//! totals back over a channel so the UI can show progress without blocking
+//! the main loop. Cancellation is cooperative: the worker checks an
+//! `AtomicBool` between entries and stops as soon as it sees it set.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+};
+
+/// `(root, bytes_so_far, done)` sent from the scan thread to the UI.
+pub type ScanMessage = (PathBuf, u64, bool);
+
+/// How many entries the walker visits between progress messages.
+const PROGRESS_STRIDE: u64 = 64;
+
+/// A running (or finished) scan of one directory.
+pub struct ScanHandle {
+    root: PathBuf,
+    cancel: Arc<AtomicBool>,
+    rx: mpsc::Receiver<ScanMessage>,
+}
+
+impl ScanHandle {
+    /// Spawn a worker thread that walks `root` and streams size updates.
+    pub fn spawn(root: PathBuf) -> Self {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel();
+
+        let walk_root = root.clone();
+        let walk_cancel = Arc::clone(&cancel);
+        thread::spawn(move || {
+            let mut running = 0u64;
+            let mut seen = 0u64;
+            walk(&walk_root, &walk_root, &walk_cancel, &mut running, &mut seen, &tx);
+            // Always send a final message so the UI can stop showing "scanning",
+            // even if we were cancelled partway through.
+            let _ = tx.send((walk_root, running, true));
+        });
+
+        Self { root, cancel, rx }
+    }
+
+    /// The directory this handle is scanning.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Ask the worker to stop at its next opportunity.
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// Drain all messages currently buffered without blocking.
+    pub fn drain(&self) -> Vec<ScanMessage> {
+        self.rx.try_iter().collect()
+    }
+}
+
+/// Recursively walk `dir`, adding bottom-up to `running` (the total for the
+/// whole scan rooted at `root`) and sending periodic progress messages
+/// tagged with `root` so the UI can key them into `scanned_size`.
+fn walk(
+    root: &Path,
+    dir: &Path,
+    cancel: &AtomicBool,
+    running: &mut u64,
+    seen: &mut u64,
+    tx: &mpsc::Sender<ScanMessage>,
+) {
+    let read = match fs::read_dir(dir) {
+        Ok(r) => r,
+        Err(_) => return,
+    };
+
+    for item in read {
+        if cancel.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let Ok(item) = item else { continue };
+
+        // Don't follow symlinks: avoids cycles and double-counting.
+        let md = match item.path().symlink_metadata() {
+            Ok(m) => m,
+            Err(_) => continue,
+        };
+
+        if md.is_symlink() {
+            continue;
+        }
+
+        if md.is_dir() {
+            walk(root, &item.path(), cancel, running, seen, tx);
+        } else {
+            *running += md.len();
+        }
+
+        *seen += 1;
+        if seen.is_multiple_of(PROGRESS_STRIDE) {
+            let _ = tx.send((root.to_path_buf(), *running, false));
+        }
+    }
+}
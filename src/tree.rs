@@ -0,0 +1,151 @@
+//! Collapsible tree view: lazily-loaded directory nodes flattened into a
+//! single visible list for rendering and selection.
+
+use std::{fs, path::Path};
+
+use crate::Entry;
+
+/// One node in the tree. Directories load their children lazily the first
+/// time they are expanded; `children` stays `None` until then.
+pub struct TreeNode {
+    pub entry: Entry,
+    pub depth: u8,
+    pub expanded: bool,
+    pub children: Option<Vec<TreeNode>>,
+}
+
+impl TreeNode {
+    fn new(entry: Entry, depth: u8) -> Self {
+        Self {
+            entry,
+            depth,
+            expanded: false,
+            children: None,
+        }
+    }
+}
+
+/// One row of the flattened, currently-visible tree, ready to render.
+pub struct FlatRow {
+    pub entry: Entry,
+    pub expanded: bool,
+    /// Precomputed ASCII branch prefix (e.g. `"│  ├─ "`).
+    pub prefix: String,
+}
+
+/// Build the top-level (depth 0) nodes for `dir`, collapsed by default.
+pub fn build_roots(dir: &Path) -> Vec<TreeNode> {
+    load_children(dir, 0)
+}
+
+/// Read and sort one directory's entries into tree nodes at `depth`.
+/// Read errors simply produce an empty node list (mirrors `App::refresh`,
+/// which surfaces read errors via `last_error` instead of crashing).
+fn load_children(dir: &Path, depth: u8) -> Vec<TreeNode> {
+    let mut nodes: Vec<TreeNode> = Vec::new();
+
+    let Ok(read) = fs::read_dir(dir) else {
+        return nodes;
+    };
+
+    for item in read.flatten() {
+        let path = item.path();
+        let name = item.file_name().to_string_lossy().to_string();
+        let md = item.metadata().ok();
+        let is_dir = md.as_ref().map(|m| m.is_dir()).unwrap_or(false);
+        let size = md
+            .as_ref()
+            .and_then(|m| if m.is_file() { Some(m.len()) } else { None });
+        let modified = md.as_ref().and_then(|m| m.modified().ok());
+
+        nodes.push(TreeNode::new(
+            Entry {
+                name,
+                path,
+                is_dir,
+                size,
+                modified,
+            },
+            depth,
+        ));
+    }
+
+    nodes.sort_by(|a, b| match (a.entry.is_dir, b.entry.is_dir) {
+        (true, false) => std::cmp::Ordering::Less,
+        (false, true) => std::cmp::Ordering::Greater,
+        _ => a.entry.name.to_lowercase().cmp(&b.entry.name.to_lowercase()),
+    });
+
+    nodes
+}
+
+/// Toggle the expanded state of the node at visible index `index`,
+/// lazily loading its children the first time it is expanded.
+pub fn toggle_visible(roots: &mut [TreeNode], index: usize) {
+    let mut remaining = index;
+    if let Some(node) = find_visible_mut(roots, &mut remaining) {
+        if node.entry.is_dir {
+            if node.children.is_none() {
+                node.children = Some(load_children(&node.entry.path, node.depth + 1));
+            }
+            node.expanded = !node.expanded;
+        }
+    }
+}
+
+/// Walk the visible (expanded-ancestor) nodes in display order, decrementing
+/// `remaining` until it reaches zero, and return that node.
+fn find_visible_mut<'a>(nodes: &'a mut [TreeNode], remaining: &mut usize) -> Option<&'a mut TreeNode> {
+    for node in nodes.iter_mut() {
+        if *remaining == 0 {
+            return Some(node);
+        }
+        *remaining -= 1;
+
+        if node.expanded {
+            if let Some(children) = node.children.as_mut() {
+                if let Some(found) = find_visible_mut(children, remaining) {
+                    return Some(found);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Flatten the visible (expanded-ancestor) portion of the tree into rows
+/// ready for rendering, with ASCII branch prefixes.
+pub fn flatten(roots: &[TreeNode]) -> Vec<FlatRow> {
+    let mut out = Vec::new();
+    flatten_rec(roots, &mut Vec::new(), &mut out);
+    out
+}
+
+fn flatten_rec(nodes: &[TreeNode], ancestors_last: &mut Vec<bool>, out: &mut Vec<FlatRow>) {
+    let len = nodes.len();
+    for (i, node) in nodes.iter().enumerate() {
+        let is_last = i + 1 == len;
+        out.push(FlatRow {
+            entry: node.entry.clone(),
+            expanded: node.expanded,
+            prefix: branch_prefix(ancestors_last, is_last),
+        });
+
+        if node.expanded {
+            if let Some(children) = &node.children {
+                ancestors_last.push(is_last);
+                flatten_rec(children, ancestors_last, out);
+                ancestors_last.pop();
+            }
+        }
+    }
+}
+
+fn branch_prefix(ancestors_last: &[bool], is_last: bool) -> String {
+    let mut prefix = String::new();
+    for &last in ancestors_last {
+        prefix.push_str(if last { "   " } else { "│  " });
+    }
+    prefix.push_str(if is_last { "└─ " } else { "├─ " });
+    prefix
+}
@@ -0,0 +1,40 @@
+//! Watches one directory for external changes and lets the caller poll for
+//! "something changed" without blocking. Debouncing bursts of events is the
+//! caller's job (see `App::poll_watcher`) since that's tied to the UI's own
+//! tick rate.
+
+use std::{path::Path, sync::mpsc};
+
+use notify::{Event, RecommendedWatcher, RecursiveMode, Result as NotifyResult, Watcher as _};
+
+/// Watches a directory for creates/removes/renames/modifies.
+pub struct Watcher {
+    // Kept alive only so the watch isn't dropped; never read directly.
+    _inner: RecommendedWatcher,
+    rx: mpsc::Receiver<NotifyResult<Event>>,
+}
+
+impl Watcher {
+    /// Start watching `dir` (non-recursively — subdirectories get their own
+    /// watch when the user navigates into them).
+    pub fn new(dir: &Path) -> NotifyResult<Self> {
+        let (tx, rx) = mpsc::channel();
+        let mut inner = notify::recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        })?;
+        inner.watch(dir, RecursiveMode::NonRecursive)?;
+
+        Ok(Self { _inner: inner, rx })
+    }
+
+    /// Drain all events currently buffered, returning whether any arrived.
+    /// Errors from the watcher are treated the same as events: they mean
+    /// something about the watch is worth a refresh (or a fresh watcher).
+    pub fn poll(&self) -> bool {
+        let mut any = false;
+        while self.rx.try_recv().is_ok() {
+            any = true;
+        }
+        any
+    }
+}